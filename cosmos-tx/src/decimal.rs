@@ -0,0 +1,131 @@
+//! Fixed-point decimal values
+
+use crate::{Denom, Result};
+use std::fmt;
+
+/// Number of decimal digits of precision a [`Decimal`] carries.
+const DECIMAL_PLACES: u32 = 18;
+
+/// `10^DECIMAL_PLACES`, the scaling factor between a [`Decimal`]'s internal
+/// representation and its integer part.
+const PRECISION: u128 = 1_000_000_000_000_000_000;
+
+/// A non-negative fixed-point decimal number with 18 digits of precision,
+/// mirroring the Cosmos SDK's `sdk.Dec`. Used for values like gas prices,
+/// which are rarely whole numbers of a coin's smallest denomination.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, PartialOrd, Ord)]
+pub struct Decimal {
+    /// The value, scaled up by [`PRECISION`] and truncated to an integer.
+    atomics: u128,
+}
+
+impl Decimal {
+    /// The zero value.
+    pub const fn zero() -> Decimal {
+        Decimal { atomics: 0 }
+    }
+
+    /// Computes `numerator / denominator`, keeping [`DECIMAL_PLACES`] digits
+    /// of precision.
+    ///
+    /// Returns an error on overflow or if `denominator` is zero.
+    pub fn from_ratio(numerator: u128, denominator: u128) -> Result<Decimal> {
+        if denominator == 0 {
+            return Err(eyre::eyre!("cannot compute a decimal ratio with a zero denominator"));
+        }
+
+        let atomics = numerator
+            .checked_mul(PRECISION)
+            .ok_or_else(|| eyre::eyre!("overflow computing decimal ratio {}/{}", numerator, denominator))?
+            / denominator;
+
+        Ok(Decimal { atomics })
+    }
+
+    /// Multiplies this value by the integer `rhs`.
+    pub fn checked_mul(&self, rhs: u128) -> Result<Decimal> {
+        let atomics = self
+            .atomics
+            .checked_mul(rhs)
+            .ok_or_else(|| eyre::eyre!("overflow multiplying decimal {} by {}", self, rhs))?;
+
+        Ok(Decimal { atomics })
+    }
+
+    /// Rounds this value up to the nearest integer.
+    pub fn ceil(&self) -> u128 {
+        let quotient = self.atomics / PRECISION;
+        let remainder = self.atomics % PRECISION;
+
+        if remainder == 0 {
+            quotient
+        } else {
+            quotient + 1
+        }
+    }
+}
+
+impl fmt::Display for Decimal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}.{:0width$}",
+            self.atomics / PRECISION,
+            self.atomics % PRECISION,
+            width = DECIMAL_PLACES as usize
+        )
+    }
+}
+
+/// A [`Coin`](crate::Coin)-like amount whose value is a fractional
+/// [`Decimal`] rather than an integer, e.g. as used to express a minimum
+/// gas price.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DecCoin {
+    /// Denomination of this coin.
+    pub denom: Denom,
+
+    /// Fractional amount of this coin.
+    pub amount: Decimal,
+}
+
+impl DecCoin {
+    /// Constructs a new [`DecCoin`] from the given `denom` and `amount`.
+    pub fn new(amount: Decimal, denom: Denom) -> DecCoin {
+        DecCoin { denom, amount }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_ratio_keeps_precision() {
+        let dec = Decimal::from_ratio(1, 3).unwrap();
+        assert_eq!(dec.ceil(), 1);
+        assert_eq!(dec.to_string(), "0.333333333333333333");
+    }
+
+    #[test]
+    fn from_ratio_rejects_zero_denominator() {
+        assert!(Decimal::from_ratio(1, 0).is_err());
+    }
+
+    #[test]
+    fn from_ratio_rejects_overflow() {
+        assert!(Decimal::from_ratio(u128::MAX, 1).is_err());
+    }
+
+    #[test]
+    fn checked_mul_rejects_overflow() {
+        let dec = Decimal::from_ratio(1, 1).unwrap();
+        assert!(dec.checked_mul(u128::MAX).is_err());
+    }
+
+    #[test]
+    fn ceil_rounds_up_only_when_there_is_a_remainder() {
+        assert_eq!(Decimal::from_ratio(4, 2).unwrap().ceil(), 2);
+        assert_eq!(Decimal::from_ratio(5, 2).unwrap().ceil(), 3);
+    }
+}
@@ -0,0 +1,523 @@
+//! Fee allowances (`x/feegrant`)
+//!
+//! Lets one account ("granter") authorize another ("grantee") to pay
+//! transaction fees from the granter's balance, which is what makes
+//! [`Fee::granter`](crate::tx::Fee::granter) usable end-to-end. Only one
+//! grant may exist per granter/grantee pair, and an account may not grant
+//! an allowance to itself.
+
+use crate::{Coin, Result};
+use cosmos_sdk_proto::cosmos::feegrant::v1beta1 as feegrant;
+use cosmos_sdk_proto::prost::Message;
+use cosmos_sdk_proto::prost_types::{Duration, Timestamp};
+use cosmos_sdk_proto::Any;
+use std::convert::{TryFrom, TryInto};
+
+use crate::AccountId;
+
+const BASIC_ALLOWANCE_TYPE_URL: &str = "/cosmos.feegrant.v1beta1.BasicAllowance";
+const PERIODIC_ALLOWANCE_TYPE_URL: &str = "/cosmos.feegrant.v1beta1.PeriodicAllowance";
+const ALLOWED_MSG_ALLOWANCE_TYPE_URL: &str = "/cosmos.feegrant.v1beta1.AllowedMsgAllowance";
+
+/// An allowance that permits the grantee to spend up to `spend_limit`,
+/// optionally expiring at a fixed point in time.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BasicAllowance {
+    /// The maximum amount the grantee can spend, across all fees paid
+    /// under this grant. An empty list means unlimited.
+    pub spend_limit: Vec<Coin>,
+
+    /// The point in time after which this allowance is no longer valid.
+    pub expiration: Option<Timestamp>,
+}
+
+/// An allowance that resets `period_spend_limit` every `period`, in
+/// addition to enforcing the overall `basic` limit.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PeriodicAllowance {
+    /// The overall (non-resetting) limit backing this allowance.
+    pub basic: BasicAllowance,
+
+    /// The time duration in which `period_spend_limit` is replenished.
+    pub period: Duration,
+
+    /// The maximum amount that can be spent within a single period.
+    pub period_spend_limit: Vec<Coin>,
+
+    /// The amount left to spend in the current period.
+    pub period_can_spend: Vec<Coin>,
+
+    /// The time at which the current period resets.
+    pub period_reset: Timestamp,
+}
+
+/// An allowance that restricts an underlying allowance to a set of message
+/// type URLs, e.g. permitting fees to be paid only for `MsgSend`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AllowedMsgAllowance {
+    /// The allowance being restricted.
+    pub allowance: Box<Allowance>,
+
+    /// Msg type URLs the grantee is allowed to use this allowance for.
+    pub allowed_messages: Vec<String>,
+}
+
+/// A fee allowance granted by one account to another.
+///
+/// Wraps the three `x/feegrant` allowance variants and knows how to pack
+/// and unpack itself into the [`Any`] that
+/// `MsgGrantAllowance`/`MsgRevokeAllowance` expect.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Allowance {
+    /// A [`BasicAllowance`].
+    Basic(BasicAllowance),
+
+    /// A [`PeriodicAllowance`].
+    Periodic(PeriodicAllowance),
+
+    /// An [`AllowedMsgAllowance`].
+    AllowedMsg(AllowedMsgAllowance),
+}
+
+impl Allowance {
+    /// Pack this allowance into the [`Any`] type the feegrant proto types
+    /// expect.
+    pub fn to_any(&self) -> Result<Any> {
+        Any::try_from(self)
+    }
+}
+
+impl TryFrom<feegrant::BasicAllowance> for BasicAllowance {
+    type Error = eyre::Report;
+
+    fn try_from(proto: feegrant::BasicAllowance) -> Result<BasicAllowance> {
+        Ok(BasicAllowance {
+            spend_limit: proto
+                .spend_limit
+                .iter()
+                .map(TryFrom::try_from)
+                .collect::<Result<_, _>>()?,
+            expiration: proto.expiration,
+        })
+    }
+}
+
+impl From<&BasicAllowance> for feegrant::BasicAllowance {
+    fn from(allowance: &BasicAllowance) -> feegrant::BasicAllowance {
+        feegrant::BasicAllowance {
+            spend_limit: allowance.spend_limit.iter().map(Into::into).collect(),
+            expiration: allowance.expiration.clone(),
+        }
+    }
+}
+
+impl TryFrom<feegrant::PeriodicAllowance> for PeriodicAllowance {
+    type Error = eyre::Report;
+
+    fn try_from(proto: feegrant::PeriodicAllowance) -> Result<PeriodicAllowance> {
+        Ok(PeriodicAllowance {
+            basic: proto
+                .basic
+                .ok_or_else(|| eyre::eyre!("periodic allowance missing basic allowance"))?
+                .try_into()?,
+            period: proto
+                .period
+                .ok_or_else(|| eyre::eyre!("periodic allowance missing period"))?,
+            period_spend_limit: proto
+                .period_spend_limit
+                .iter()
+                .map(TryFrom::try_from)
+                .collect::<Result<_, _>>()?,
+            period_can_spend: proto
+                .period_can_spend
+                .iter()
+                .map(TryFrom::try_from)
+                .collect::<Result<_, _>>()?,
+            period_reset: proto
+                .period_reset
+                .ok_or_else(|| eyre::eyre!("periodic allowance missing period reset"))?,
+        })
+    }
+}
+
+impl From<&PeriodicAllowance> for feegrant::PeriodicAllowance {
+    fn from(allowance: &PeriodicAllowance) -> feegrant::PeriodicAllowance {
+        feegrant::PeriodicAllowance {
+            basic: Some((&allowance.basic).into()),
+            period: Some(allowance.period.clone()),
+            period_spend_limit: allowance.period_spend_limit.iter().map(Into::into).collect(),
+            period_can_spend: allowance.period_can_spend.iter().map(Into::into).collect(),
+            period_reset: Some(allowance.period_reset.clone()),
+        }
+    }
+}
+
+impl TryFrom<feegrant::AllowedMsgAllowance> for AllowedMsgAllowance {
+    type Error = eyre::Report;
+
+    fn try_from(proto: feegrant::AllowedMsgAllowance) -> Result<AllowedMsgAllowance> {
+        let allowance = proto
+            .allowance
+            .ok_or_else(|| eyre::eyre!("allowed msg allowance missing inner allowance"))?;
+
+        Ok(AllowedMsgAllowance {
+            allowance: Box::new(Allowance::try_from(allowance)?),
+            allowed_messages: proto.allowed_messages,
+        })
+    }
+}
+
+impl TryFrom<&AllowedMsgAllowance> for feegrant::AllowedMsgAllowance {
+    type Error = eyre::Report;
+
+    fn try_from(allowance: &AllowedMsgAllowance) -> Result<feegrant::AllowedMsgAllowance> {
+        Ok(feegrant::AllowedMsgAllowance {
+            allowance: Some(allowance.allowance.to_any()?),
+            allowed_messages: allowance.allowed_messages.clone(),
+        })
+    }
+}
+
+impl TryFrom<&Allowance> for Any {
+    type Error = eyre::Report;
+
+    fn try_from(allowance: &Allowance) -> Result<Any> {
+        let (type_url, value) = match allowance {
+            Allowance::Basic(basic) => (
+                BASIC_ALLOWANCE_TYPE_URL,
+                feegrant::BasicAllowance::from(basic).encode_to_vec(),
+            ),
+            Allowance::Periodic(periodic) => (
+                PERIODIC_ALLOWANCE_TYPE_URL,
+                feegrant::PeriodicAllowance::from(periodic).encode_to_vec(),
+            ),
+            Allowance::AllowedMsg(allowed_msg) => (
+                ALLOWED_MSG_ALLOWANCE_TYPE_URL,
+                feegrant::AllowedMsgAllowance::try_from(allowed_msg)?.encode_to_vec(),
+            ),
+        };
+
+        Ok(Any {
+            type_url: type_url.to_owned(),
+            value,
+        })
+    }
+}
+
+impl TryFrom<Allowance> for Any {
+    type Error = eyre::Report;
+
+    fn try_from(allowance: Allowance) -> Result<Any> {
+        Any::try_from(&allowance)
+    }
+}
+
+impl TryFrom<&Any> for Allowance {
+    type Error = eyre::Report;
+
+    fn try_from(any: &Any) -> Result<Allowance> {
+        match any.type_url.as_str() {
+            BASIC_ALLOWANCE_TYPE_URL => Ok(Allowance::Basic(
+                feegrant::BasicAllowance::decode(any.value.as_slice())?.try_into()?,
+            )),
+            PERIODIC_ALLOWANCE_TYPE_URL => Ok(Allowance::Periodic(
+                feegrant::PeriodicAllowance::decode(any.value.as_slice())?.try_into()?,
+            )),
+            ALLOWED_MSG_ALLOWANCE_TYPE_URL => Ok(Allowance::AllowedMsg(
+                feegrant::AllowedMsgAllowance::decode(any.value.as_slice())?.try_into()?,
+            )),
+            other => Err(eyre::eyre!("unknown fee allowance type URL: {}", other)),
+        }
+    }
+}
+
+impl TryFrom<Any> for Allowance {
+    type Error = eyre::Report;
+
+    fn try_from(any: Any) -> Result<Allowance> {
+        Allowance::try_from(&any)
+    }
+}
+
+/// `MsgGrantAllowance` grants a fee allowance to the grantee on behalf of
+/// the granter.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MsgGrantAllowance {
+    /// The account granting the allowance.
+    pub granter: AccountId,
+
+    /// The account being granted the allowance.
+    pub grantee: AccountId,
+
+    /// The allowance being granted.
+    pub allowance: Allowance,
+}
+
+impl TryFrom<&MsgGrantAllowance> for feegrant::MsgGrantAllowance {
+    type Error = eyre::Report;
+
+    fn try_from(msg: &MsgGrantAllowance) -> Result<feegrant::MsgGrantAllowance> {
+        Ok(feegrant::MsgGrantAllowance {
+            granter: msg.granter.to_string(),
+            grantee: msg.grantee.to_string(),
+            allowance: Some(msg.allowance.to_any()?),
+        })
+    }
+}
+
+impl TryFrom<MsgGrantAllowance> for feegrant::MsgGrantAllowance {
+    type Error = eyre::Report;
+
+    fn try_from(msg: MsgGrantAllowance) -> Result<feegrant::MsgGrantAllowance> {
+        feegrant::MsgGrantAllowance::try_from(&msg)
+    }
+}
+
+impl TryFrom<&feegrant::MsgGrantAllowance> for MsgGrantAllowance {
+    type Error = eyre::Report;
+
+    fn try_from(proto: &feegrant::MsgGrantAllowance) -> Result<MsgGrantAllowance> {
+        let allowance = proto
+            .allowance
+            .as_ref()
+            .ok_or_else(|| eyre::eyre!("grant allowance message missing allowance"))?;
+
+        Ok(MsgGrantAllowance {
+            granter: proto.granter.parse()?,
+            grantee: proto.grantee.parse()?,
+            allowance: Allowance::try_from(allowance)?,
+        })
+    }
+}
+
+impl TryFrom<feegrant::MsgGrantAllowance> for MsgGrantAllowance {
+    type Error = eyre::Report;
+
+    fn try_from(proto: feegrant::MsgGrantAllowance) -> Result<MsgGrantAllowance> {
+        MsgGrantAllowance::try_from(&proto)
+    }
+}
+
+/// `MsgRevokeAllowance` removes any existing allowance from the granter to
+/// the grantee.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MsgRevokeAllowance {
+    /// The account that originally granted the allowance.
+    pub granter: AccountId,
+
+    /// The account whose allowance is being revoked.
+    pub grantee: AccountId,
+}
+
+impl From<&MsgRevokeAllowance> for feegrant::MsgRevokeAllowance {
+    fn from(msg: &MsgRevokeAllowance) -> feegrant::MsgRevokeAllowance {
+        feegrant::MsgRevokeAllowance {
+            granter: msg.granter.to_string(),
+            grantee: msg.grantee.to_string(),
+        }
+    }
+}
+
+impl From<MsgRevokeAllowance> for feegrant::MsgRevokeAllowance {
+    fn from(msg: MsgRevokeAllowance) -> feegrant::MsgRevokeAllowance {
+        feegrant::MsgRevokeAllowance::from(&msg)
+    }
+}
+
+impl TryFrom<&feegrant::MsgRevokeAllowance> for MsgRevokeAllowance {
+    type Error = eyre::Report;
+
+    fn try_from(proto: &feegrant::MsgRevokeAllowance) -> Result<MsgRevokeAllowance> {
+        Ok(MsgRevokeAllowance {
+            granter: proto.granter.parse()?,
+            grantee: proto.grantee.parse()?,
+        })
+    }
+}
+
+impl TryFrom<feegrant::MsgRevokeAllowance> for MsgRevokeAllowance {
+    type Error = eyre::Report;
+
+    fn try_from(proto: feegrant::MsgRevokeAllowance) -> Result<MsgRevokeAllowance> {
+        MsgRevokeAllowance::try_from(&proto)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use cosmos_sdk_proto::prost_types::{Duration, Timestamp};
+
+    fn denom(s: &str) -> crate::Denom {
+        s.parse().unwrap()
+    }
+
+    fn coin(amount: u128, denom_str: &str) -> Coin {
+        Coin {
+            denom: denom(denom_str),
+            amount,
+        }
+    }
+
+    #[test]
+    fn basic_allowance_round_trips_through_any() {
+        let allowance = Allowance::Basic(BasicAllowance {
+            spend_limit: vec![coin(100, "uatom")],
+            expiration: Some(Timestamp {
+                seconds: 100,
+                nanos: 0,
+            }),
+        });
+
+        let any = allowance.to_any().unwrap();
+        assert_eq!(any.type_url, BASIC_ALLOWANCE_TYPE_URL);
+        assert_eq!(Allowance::try_from(&any).unwrap(), allowance);
+    }
+
+    #[test]
+    fn periodic_allowance_round_trips_through_any() {
+        let allowance = Allowance::Periodic(PeriodicAllowance {
+            basic: BasicAllowance {
+                spend_limit: vec![coin(100, "uatom")],
+                expiration: None,
+            },
+            period: Duration {
+                seconds: 60,
+                nanos: 0,
+            },
+            period_spend_limit: vec![coin(10, "uatom")],
+            period_can_spend: vec![coin(5, "uatom")],
+            period_reset: Timestamp {
+                seconds: 160,
+                nanos: 0,
+            },
+        });
+
+        let any = allowance.to_any().unwrap();
+        assert_eq!(any.type_url, PERIODIC_ALLOWANCE_TYPE_URL);
+        assert_eq!(Allowance::try_from(&any).unwrap(), allowance);
+    }
+
+    #[test]
+    fn allowed_msg_allowance_round_trips_through_any() {
+        let allowance = Allowance::AllowedMsg(AllowedMsgAllowance {
+            allowance: Box::new(Allowance::Basic(BasicAllowance {
+                spend_limit: vec![coin(100, "uatom")],
+                expiration: None,
+            })),
+            allowed_messages: vec!["/cosmos.bank.v1beta1.MsgSend".to_owned()],
+        });
+
+        let any = allowance.to_any().unwrap();
+        assert_eq!(any.type_url, ALLOWED_MSG_ALLOWANCE_TYPE_URL);
+        assert_eq!(Allowance::try_from(&any).unwrap(), allowance);
+    }
+
+    #[test]
+    fn unknown_type_url_is_rejected() {
+        let any = Any {
+            type_url: "/cosmos.bank.v1beta1.MsgSend".to_owned(),
+            value: vec![],
+        };
+
+        assert!(Allowance::try_from(&any).is_err());
+    }
+
+    fn account_id(s: &str) -> AccountId {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn msg_grant_allowance_round_trips() {
+        let msg = MsgGrantAllowance {
+            granter: account_id("cosmos1qyqszqgpqyqszqgpqyqszqgpqyqszqgpjnp7du"),
+            grantee: account_id("cosmos1qgpqyqszqgpqyqszqgpqyqszqgpqyqszrh8mx2"),
+            allowance: Allowance::Basic(BasicAllowance {
+                spend_limit: vec![coin(100, "uatom")],
+                expiration: None,
+            }),
+        };
+
+        let proto = feegrant::MsgGrantAllowance::try_from(&msg).unwrap();
+        assert_eq!(MsgGrantAllowance::try_from(&proto).unwrap(), msg);
+    }
+
+    #[test]
+    fn msg_grant_allowance_rejects_a_missing_allowance() {
+        let proto = feegrant::MsgGrantAllowance {
+            granter: account_id("cosmos1qyqszqgpqyqszqgpqyqszqgpqyqszqgpjnp7du").to_string(),
+            grantee: account_id("cosmos1qgpqyqszqgpqyqszqgpqyqszqgpqyqszrh8mx2").to_string(),
+            allowance: None,
+        };
+
+        assert!(MsgGrantAllowance::try_from(&proto).is_err());
+    }
+
+    #[test]
+    fn msg_revoke_allowance_round_trips() {
+        let msg = MsgRevokeAllowance {
+            granter: account_id("cosmos1qyqszqgpqyqszqgpqyqszqgpqyqszqgpjnp7du"),
+            grantee: account_id("cosmos1qgpqyqszqgpqyqszqgpqyqszqgpqyqszrh8mx2"),
+        };
+
+        let proto = feegrant::MsgRevokeAllowance::from(&msg);
+        assert_eq!(MsgRevokeAllowance::try_from(&proto).unwrap(), msg);
+    }
+
+    #[test]
+    fn periodic_allowance_rejects_missing_basic_allowance() {
+        let proto = feegrant::PeriodicAllowance {
+            basic: None,
+            period: Some(Duration {
+                seconds: 60,
+                nanos: 0,
+            }),
+            period_spend_limit: vec![],
+            period_can_spend: vec![],
+            period_reset: Some(Timestamp {
+                seconds: 160,
+                nanos: 0,
+            }),
+        };
+
+        assert!(PeriodicAllowance::try_from(proto).is_err());
+    }
+
+    #[test]
+    fn periodic_allowance_rejects_missing_period() {
+        let proto = feegrant::PeriodicAllowance {
+            basic: Some(feegrant::BasicAllowance {
+                spend_limit: vec![],
+                expiration: None,
+            }),
+            period: None,
+            period_spend_limit: vec![],
+            period_can_spend: vec![],
+            period_reset: Some(Timestamp {
+                seconds: 160,
+                nanos: 0,
+            }),
+        };
+
+        assert!(PeriodicAllowance::try_from(proto).is_err());
+    }
+
+    #[test]
+    fn periodic_allowance_rejects_missing_period_reset() {
+        let proto = feegrant::PeriodicAllowance {
+            basic: Some(feegrant::BasicAllowance {
+                spend_limit: vec![],
+                expiration: None,
+            }),
+            period: Some(Duration {
+                seconds: 60,
+                nanos: 0,
+            }),
+            period_spend_limit: vec![],
+            period_can_spend: vec![],
+            period_reset: None,
+        };
+
+        assert!(PeriodicAllowance::try_from(proto).is_err());
+    }
+}
@@ -1,7 +1,7 @@
 //! Transaction fees
 
 use super::Gas;
-use crate::{AccountId, Coin, Result};
+use crate::{AccountId, Coin, DecCoin, Decimal, Denom, Result};
 use cosmos_sdk_proto::cosmos;
 use std::convert::TryFrom;
 
@@ -48,6 +48,59 @@ impl Fee {
             granter: None,
         }
     }
+
+    /// Computes the effective gas price this fee pays in `denom`: the
+    /// amount of `denom` in [`amount`](Self::amount) divided by
+    /// [`gas_limit`](Self::gas_limit).
+    ///
+    /// Returns an error if `amount` has no [`Coin`] in `denom`, or if
+    /// `gas_limit` is zero.
+    pub fn gas_price(&self, denom: &Denom) -> Result<Decimal> {
+        let coin = self
+            .amount
+            .iter()
+            .find(|coin| &coin.denom == denom)
+            .ok_or_else(|| eyre::eyre!("fee has no amount in denom `{}`", denom))?;
+
+        if self.gas_limit.value() == 0 {
+            return Err(eyre::eyre!(
+                "cannot compute a gas price with a zero gas limit"
+            ));
+        }
+
+        Decimal::from_ratio(coin.amount, self.gas_limit.value() as u128)
+    }
+
+    /// Checks whether this fee's effective gas price meets or exceeds every
+    /// entry in `min`, e.g. a node's configured `minimum-gas-prices`.
+    ///
+    /// A fee passes if, for every denom `min` cares about, this fee's
+    /// [`gas_price`](Self::gas_price) in that denom is at least the
+    /// configured minimum. A fee with no amount in one of `min`'s denoms
+    /// does not pass.
+    pub fn is_above_min_gas_price(&self, min: &[DecCoin]) -> bool {
+        min.iter().all(|min_price| {
+            self.gas_price(&min_price.denom)
+                .map(|price| price >= min_price.amount)
+                .unwrap_or(false)
+        })
+    }
+
+    /// Validates that, if [`payer`](Self::payer) is set, it appears among
+    /// `signers` — the documented invariant that the payer must be a tx
+    /// signer and thus have signed this field in `AuthInfo`.
+    pub fn validate_signers(&self, signers: &[AccountId]) -> Result<()> {
+        if let Some(payer) = &self.payer {
+            if !signers.contains(payer) {
+                return Err(eyre::eyre!(
+                    "fee payer `{}` is not among the transaction's signers",
+                    payer
+                ));
+            }
+        }
+
+        Ok(())
+    }
 }
 
 impl TryFrom<cosmos::tx::v1beta1::Fee> for Fee {
@@ -75,7 +128,7 @@ impl TryFrom<&cosmos::tx::v1beta1::Fee> for Fee {
             if id.is_empty() {
                 accounts[index] = None;
             } else {
-                accounts[index] = Some(proto.payer.parse()?)
+                accounts[index] = Some(id.parse()?)
             }
         }
 
@@ -111,4 +164,298 @@ impl From<&Fee> for cosmos::tx::v1beta1::Fee {
                 .unwrap_or_default(),
         }
     }
+}
+
+/// Builds a [`Fee`] from a gas estimate and a price, rather than requiring
+/// the caller to precompute the [`amount`](Fee::amount) themselves.
+///
+/// Borrows its fee-parameter model from fuel-tx: a flat `gas_limit`, an
+/// optional per-byte surcharge for the transaction's encoded size, and a
+/// `gas_price` used to convert the resulting gas figure into a [`Coin`].
+#[derive(Clone, Debug)]
+pub struct FeeBuilder {
+    gas_limit: Gas,
+    tx_size_bytes: u64,
+    gas_per_byte: u64,
+    gas_price: DecCoin,
+    gas_adjustment: Option<Decimal>,
+    payer: Option<AccountId>,
+    granter: Option<AccountId>,
+}
+
+impl FeeBuilder {
+    /// Constructs a new builder for the given `gas_limit` and `gas_price`.
+    pub fn new(gas_limit: impl Into<Gas>, gas_price: DecCoin) -> FeeBuilder {
+        FeeBuilder {
+            gas_limit: gas_limit.into(),
+            tx_size_bytes: 0,
+            gas_per_byte: 0,
+            gas_price,
+            gas_adjustment: None,
+            payer: None,
+            granter: None,
+        }
+    }
+
+    /// Adds a per-byte gas surcharge for the transaction's encoded size, so
+    /// that `total_gas = gas_limit + tx_size_bytes * gas_per_byte`.
+    pub fn with_tx_size(mut self, tx_size_bytes: u64, gas_per_byte: u64) -> FeeBuilder {
+        self.tx_size_bytes = tx_size_bytes;
+        self.gas_per_byte = gas_per_byte;
+        self
+    }
+
+    /// Sets a gas-adjustment multiplier (e.g. `1.3`), applied to the
+    /// simulated gas figure before the fee amount is rounded. Guards
+    /// against a simulation under-estimating the gas an actual broadcast
+    /// will consume.
+    pub fn with_gas_adjustment(mut self, gas_adjustment: Decimal) -> FeeBuilder {
+        self.gas_adjustment = Some(gas_adjustment);
+        self
+    }
+
+    /// Sets the fee [`payer`](Fee::payer).
+    pub fn with_payer(mut self, payer: AccountId) -> FeeBuilder {
+        self.payer = Some(payer);
+        self
+    }
+
+    /// Sets the fee [`granter`](Fee::granter).
+    pub fn with_granter(mut self, granter: AccountId) -> FeeBuilder {
+        self.granter = Some(granter);
+        self
+    }
+
+    /// Builds the [`Fee`]: the surcharged `total_gas` is first multiplied by
+    /// the gas adjustment (if any) and rounded up to an integer
+    /// `adjusted_gas`; `amount` is then `ceil(adjusted_gas * gas_price)` and
+    /// `gas_limit` is set to that same `adjusted_gas`, so the built fee
+    /// authorizes the gas it was actually priced for.
+    pub fn build(&self) -> Result<Fee> {
+        let surcharge = self
+            .tx_size_bytes
+            .checked_mul(self.gas_per_byte)
+            .ok_or_else(|| eyre::eyre!("overflow computing tx size gas surcharge"))?;
+
+        let total_gas = self
+            .gas_limit
+            .value()
+            .checked_add(surcharge)
+            .ok_or_else(|| eyre::eyre!("overflow computing total gas"))? as u128;
+
+        let total_gas = match self.gas_adjustment {
+            Some(adjustment) => adjustment.checked_mul(total_gas)?.ceil(),
+            None => total_gas,
+        };
+
+        let amount = self.gas_price.amount.checked_mul(total_gas)?.ceil();
+
+        let gas_limit = u64::try_from(total_gas)
+            .map_err(|_| eyre::eyre!("overflow computing fee gas limit"))?;
+
+        Ok(Fee {
+            amount: vec![Coin {
+                denom: self.gas_price.denom.clone(),
+                amount,
+            }],
+            gas_limit: gas_limit.into(),
+            payer: self.payer.clone(),
+            granter: self.granter.clone(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn denom(s: &str) -> Denom {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn gas_price_errors_on_missing_denom() {
+        let fee = Fee::from_amount_and_gas(
+            Coin {
+                denom: denom("uatom"),
+                amount: 100,
+            },
+            100u64,
+        );
+
+        assert!(fee.gas_price(&denom("uosmo")).is_err());
+    }
+
+    #[test]
+    fn gas_price_errors_on_zero_gas_limit() {
+        let fee = Fee::from_amount_and_gas(
+            Coin {
+                denom: denom("uatom"),
+                amount: 100,
+            },
+            0u64,
+        );
+
+        assert!(fee.gas_price(&denom("uatom")).is_err());
+    }
+
+    #[test]
+    fn gas_price_computes_the_effective_price() {
+        let fee = Fee::from_amount_and_gas(
+            Coin {
+                denom: denom("uatom"),
+                amount: 100,
+            },
+            10u64,
+        );
+
+        assert_eq!(
+            fee.gas_price(&denom("uatom")).unwrap(),
+            Decimal::from_ratio(100, 10).unwrap()
+        );
+    }
+
+    #[test]
+    fn is_above_min_gas_price_rejects_a_fee_below_the_minimum() {
+        let fee = Fee::from_amount_and_gas(
+            Coin {
+                denom: denom("uatom"),
+                amount: 1,
+            },
+            10u64,
+        );
+
+        let min = vec![DecCoin::new(Decimal::from_ratio(1, 1).unwrap(), denom("uatom"))];
+        assert!(!fee.is_above_min_gas_price(&min));
+    }
+
+    #[test]
+    fn is_above_min_gas_price_rejects_a_fee_missing_the_minimum_denom() {
+        let fee = Fee::from_amount_and_gas(
+            Coin {
+                denom: denom("uatom"),
+                amount: 100,
+            },
+            10u64,
+        );
+
+        let min = vec![DecCoin::new(Decimal::zero(), denom("uosmo"))];
+        assert!(!fee.is_above_min_gas_price(&min));
+    }
+
+    #[test]
+    fn is_above_min_gas_price_accepts_a_fee_meeting_the_minimum() {
+        let fee = Fee::from_amount_and_gas(
+            Coin {
+                denom: denom("uatom"),
+                amount: 100,
+            },
+            10u64,
+        );
+
+        let min = vec![DecCoin::new(Decimal::from_ratio(10, 1).unwrap(), denom("uatom"))];
+        assert!(fee.is_above_min_gas_price(&min));
+    }
+
+    #[test]
+    fn fee_builder_gas_limit_matches_the_gas_amount_was_priced_for() {
+        let gas_price = DecCoin::new(Decimal::from_ratio(1, 1).unwrap(), denom("uatom"));
+
+        let fee = FeeBuilder::new(100_000u64, gas_price)
+            .with_tx_size(200, 10)
+            .with_gas_adjustment(Decimal::from_ratio(13, 10).unwrap())
+            .build()
+            .unwrap();
+
+        // total_gas = 100_000 + 200 * 10 = 102_000, * 1.3 = 132_600
+        assert_eq!(fee.gas_limit.value(), 132_600);
+        assert_eq!(
+            fee.amount,
+            vec![Coin {
+                denom: denom("uatom"),
+                amount: 132_600,
+            }]
+        );
+    }
+
+    #[test]
+    fn fee_builder_rounds_amount_up() {
+        let gas_price = DecCoin::new(Decimal::from_ratio(1, 3).unwrap(), denom("uatom"));
+
+        let fee = FeeBuilder::new(10u64, gas_price).build().unwrap();
+
+        // 10 * (1/3) = 3.33.., rounds up to 4
+        assert_eq!(fee.amount[0].amount, 4);
+        assert_eq!(fee.gas_limit.value(), 10);
+    }
+
+    fn account_id(s: &str) -> AccountId {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn try_from_proto_parses_payer_and_granter_independently() {
+        let payer = account_id("cosmos1qyqszqgpqyqszqgpqyqszqgpqyqszqgpjnp7du");
+        let granter = account_id("cosmos1qgpqyqszqgpqyqszqgpqyqszqgpqyqszrh8mx2");
+
+        let proto = cosmos::tx::v1beta1::Fee {
+            amount: vec![],
+            gas_limit: 100,
+            payer: payer.to_string(),
+            granter: granter.to_string(),
+        };
+
+        let fee = Fee::try_from(&proto).unwrap();
+        assert_eq!(fee.payer, Some(payer));
+        assert_eq!(fee.granter, Some(granter));
+    }
+
+    #[test]
+    fn validate_signers_accepts_a_payer_in_the_signer_set() {
+        let payer = account_id("cosmos1qyqszqgpqyqszqgpqyqszqgpqyqszqgpjnp7du");
+        let other_signer = account_id("cosmos1qvpsxqcrqvpsxqcrqvpsxqcrqvpsxqcrz8x6vt");
+
+        let mut fee = Fee::from_amount_and_gas(
+            Coin {
+                denom: denom("uatom"),
+                amount: 100,
+            },
+            100u64,
+        );
+        fee.payer = Some(payer.clone());
+
+        assert!(fee
+            .validate_signers(&[other_signer, payer])
+            .is_ok());
+    }
+
+    #[test]
+    fn validate_signers_rejects_a_payer_missing_from_the_signer_set() {
+        let payer = account_id("cosmos1qyqszqgpqyqszqgpqyqszqgpqyqszqgpjnp7du");
+        let other_signer = account_id("cosmos1qvpsxqcrqvpsxqcrqvpsxqcrqvpsxqcrz8x6vt");
+
+        let mut fee = Fee::from_amount_and_gas(
+            Coin {
+                denom: denom("uatom"),
+                amount: 100,
+            },
+            100u64,
+        );
+        fee.payer = Some(payer);
+
+        assert!(fee.validate_signers(&[other_signer]).is_err());
+    }
+
+    #[test]
+    fn validate_signers_passes_trivially_when_payer_is_unset() {
+        let fee = Fee::from_amount_and_gas(
+            Coin {
+                denom: denom("uatom"),
+                amount: 100,
+            },
+            100u64,
+        );
+
+        assert!(fee.validate_signers(&[]).is_ok());
+    }
 }
\ No newline at end of file
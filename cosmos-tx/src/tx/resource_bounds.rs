@@ -0,0 +1,232 @@
+//! Multi-dimensional resource bounds
+//!
+//! An alternative to [`Fee`]'s single flat `gas_limit`, for chains and
+//! rollups that price a transaction along several independent resource
+//! axes (e.g. L1 data gas vs. L2 execution gas) rather than one gas
+//! number.
+
+use super::Fee;
+use crate::{AccountId, Coin, Denom, Result};
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// The budget for a single resource axis.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ResourceLimit {
+    /// The maximum amount of this resource the transaction may consume.
+    pub max_amount: u64,
+
+    /// The maximum price, per unit of this resource, the sender will pay.
+    pub max_price_per_unit: u128,
+}
+
+/// A [`Fee`] expressed as a budget per resource axis instead of a single
+/// `gas_limit`, e.g. `{"l1_gas": {...}, "l2_gas": {...}}`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ResourceBounds {
+    /// Per-resource budgets, keyed by resource name.
+    pub limits: BTreeMap<String, ResourceLimit>,
+
+    /// Payer: see [`Fee::payer`].
+    pub payer: Option<AccountId>,
+
+    /// Granter: see [`Fee::granter`].
+    pub granter: Option<AccountId>,
+}
+
+impl ResourceBounds {
+    /// Computes the maximum amount the sender could possibly be charged
+    /// across all resource axes, for balance pre-checks.
+    pub fn max_total_fee(&self) -> Result<u128> {
+        self.limits.values().try_fold(0u128, |total, limit| {
+            let max_for_axis = (limit.max_amount as u128)
+                .checked_mul(limit.max_price_per_unit)
+                .ok_or_else(|| eyre::eyre!("overflow computing max fee for a resource axis"))?;
+
+            total
+                .checked_add(max_for_axis)
+                .ok_or_else(|| eyre::eyre!("overflow computing total max fee"))
+        })
+    }
+}
+
+impl Fee {
+    /// Flattens a [`ResourceBounds`] budget into a [`Fee`] with a single
+    /// `amount` and `gas_limit`, so it still round-trips through the proto
+    /// `cosmos.tx.v1beta1.Fee`: `amount = Σ max_amount * max_price_per_unit`
+    /// priced in `denom`, and `gas_limit = Σ max_amount`.
+    pub fn from_resource_bounds(bounds: &ResourceBounds, denom: Denom) -> Result<Fee> {
+        let mut total_amount: u128 = 0;
+        let mut total_gas: u64 = 0;
+
+        for limit in bounds.limits.values() {
+            let axis_amount = (limit.max_amount as u128)
+                .checked_mul(limit.max_price_per_unit)
+                .ok_or_else(|| eyre::eyre!("overflow computing fee amount for a resource axis"))?;
+
+            total_amount = total_amount
+                .checked_add(axis_amount)
+                .ok_or_else(|| eyre::eyre!("overflow summing resource bound fee amounts"))?;
+
+            total_gas = total_gas
+                .checked_add(limit.max_amount)
+                .ok_or_else(|| eyre::eyre!("overflow summing resource bound gas limits"))?;
+        }
+
+        Ok(Fee {
+            amount: vec![Coin {
+                denom,
+                amount: total_amount,
+            }],
+            gas_limit: total_gas.into(),
+            payer: bounds.payer.clone(),
+            granter: bounds.granter.clone(),
+        })
+    }
+}
+
+/// Error returned by [`BoundsChecker::check`], identifying the first
+/// resource axis whose [`ResourceLimit::max_amount`] was exceeded.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ResourceBoundExceeded {
+    /// The resource axis whose budget was exceeded.
+    pub resource: String,
+
+    /// The amount actually consumed.
+    pub consumed: u64,
+
+    /// The budget that was exceeded.
+    pub max_amount: u64,
+}
+
+impl fmt::Display for ResourceBoundExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "resource bound exceeded on `{}`: consumed {}, max {}",
+            self.resource, self.consumed, self.max_amount
+        )
+    }
+}
+
+impl std::error::Error for ResourceBoundExceeded {}
+
+/// Checks actual resource consumption against a [`ResourceBounds`] budget.
+#[derive(Clone, Debug)]
+pub struct BoundsChecker<'a> {
+    bounds: &'a ResourceBounds,
+}
+
+impl<'a> BoundsChecker<'a> {
+    /// Constructs a checker for the given `bounds`.
+    pub fn new(bounds: &'a ResourceBounds) -> BoundsChecker<'a> {
+        BoundsChecker { bounds }
+    }
+
+    /// Checks `consumed` (actual resource usage, keyed the same as
+    /// [`ResourceBounds::limits`]) against the budget, returning the first
+    /// axis (in resource-name order) whose `max_amount` was exceeded.
+    pub fn check(
+        &self,
+        consumed: &BTreeMap<String, u64>,
+    ) -> std::result::Result<(), ResourceBoundExceeded> {
+        for (resource, limit) in &self.bounds.limits {
+            if let Some(&amount) = consumed.get(resource) {
+                if amount > limit.max_amount {
+                    return Err(ResourceBoundExceeded {
+                        resource: resource.clone(),
+                        consumed: amount,
+                        max_amount: limit.max_amount,
+                    });
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bounds(limits: &[(&str, u64, u128)]) -> ResourceBounds {
+        ResourceBounds {
+            limits: limits
+                .iter()
+                .map(|(resource, max_amount, max_price_per_unit)| {
+                    (
+                        (*resource).to_owned(),
+                        ResourceLimit {
+                            max_amount: *max_amount,
+                            max_price_per_unit: *max_price_per_unit,
+                        },
+                    )
+                })
+                .collect(),
+            payer: None,
+            granter: None,
+        }
+    }
+
+    #[test]
+    fn bounds_checker_reports_the_first_axis_exceeded_in_resource_name_order() {
+        let bounds = bounds(&[("l1_gas", 100, 1), ("l2_gas", 100, 1)]);
+        let checker = BoundsChecker::new(&bounds);
+
+        let consumed = [("l1_gas".to_owned(), 200), ("l2_gas".to_owned(), 200)]
+            .into_iter()
+            .collect::<BTreeMap<_, _>>();
+
+        let err = checker.check(&consumed).unwrap_err();
+        assert_eq!(err.resource, "l1_gas");
+        assert_eq!(err.consumed, 200);
+        assert_eq!(err.max_amount, 100);
+    }
+
+    #[test]
+    fn bounds_checker_accepts_consumption_within_budget() {
+        let bounds = bounds(&[("l1_gas", 100, 1)]);
+        let checker = BoundsChecker::new(&bounds);
+
+        let consumed = [("l1_gas".to_owned(), 100)].into_iter().collect::<BTreeMap<_, _>>();
+        assert!(checker.check(&consumed).is_ok());
+    }
+
+    #[test]
+    fn max_total_fee_sums_every_axis() {
+        let bounds = bounds(&[("l1_gas", 100, 2), ("l2_gas", 50, 3)]);
+        assert_eq!(bounds.max_total_fee().unwrap(), 100 * 2 + 50 * 3);
+    }
+
+    #[test]
+    fn max_total_fee_errors_on_overflow() {
+        let bounds = bounds(&[("l1_gas", u64::MAX, u128::MAX)]);
+        assert!(bounds.max_total_fee().is_err());
+    }
+
+    #[test]
+    fn from_resource_bounds_sums_amount_and_gas_limit_across_axes() {
+        let bounds = bounds(&[("l1_gas", 100, 2), ("l2_gas", 50, 3)]);
+
+        let fee = Fee::from_resource_bounds(&bounds, "uatom".parse().unwrap()).unwrap();
+
+        assert_eq!(fee.amount, vec![Coin {
+            denom: "uatom".parse().unwrap(),
+            amount: 100 * 2 + 50 * 3,
+        }]);
+        assert_eq!(fee.gas_limit.value(), 100 + 50);
+    }
+
+    #[test]
+    fn from_resource_bounds_errors_on_amount_overflow() {
+        let bounds = bounds(&[("l1_gas", u64::MAX, u128::MAX)]);
+        assert!(Fee::from_resource_bounds(&bounds, "uatom".parse().unwrap()).is_err());
+    }
+
+    #[test]
+    fn from_resource_bounds_errors_on_gas_limit_overflow() {
+        let bounds = bounds(&[("l1_gas", u64::MAX, 1), ("l2_gas", 1, 1)]);
+        assert!(Fee::from_resource_bounds(&bounds, "uatom".parse().unwrap()).is_err());
+    }
+}